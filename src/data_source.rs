@@ -0,0 +1,80 @@
+use crate::{read_csv, Ohlcv};
+use chrono::NaiveDate;
+
+/// A source of daily OHLCV bars for a symbol over a date range, abstracting over
+/// where the data actually comes from (local CSV export, a live market-data API, ...).
+/// Implementations map whatever they fetch into the existing `Ohlcv` shape so
+/// `run_strategy` doesn't need to know or care which source produced it.
+pub trait DataSource {
+    fn fetch(&self, symbol: &str, start: NaiveDate, end: NaiveDate) -> Result<Vec<Ohlcv>, String>;
+}
+
+/// Reads a single local CSV file, ignoring `symbol`/`start`/`end` since the file is
+/// already scoped to one ticker and date range by whoever exported it.
+pub struct CsvSource {
+    path: String,
+}
+
+impl CsvSource {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl DataSource for CsvSource {
+    fn fetch(&self, _symbol: &str, _start: NaiveDate, _end: NaiveDate) -> Result<Vec<Ohlcv>, String> {
+        read_csv(&self.path).map_err(|e| e.to_string())
+    }
+}
+
+/// Fetches live/historical daily OHLCV from Yahoo Finance via `yahoo_finance_api`.
+pub struct YahooSource;
+
+impl YahooSource {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for YahooSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_offset_date_time(date: NaiveDate) -> Result<time::OffsetDateTime, String> {
+    let unix_seconds = date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
+    time::OffsetDateTime::from_unix_timestamp(unix_seconds).map_err(|e| e.to_string())
+}
+
+impl DataSource for YahooSource {
+    fn fetch(&self, symbol: &str, start: NaiveDate, end: NaiveDate) -> Result<Vec<Ohlcv>, String> {
+        let start_dt = to_offset_date_time(start)?;
+        let end_dt = to_offset_date_time(end)?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())?;
+        let connector = yahoo_finance_api::YahooConnector::new().map_err(|e| e.to_string())?;
+        let response = runtime
+            .block_on(connector.get_quote_history(symbol, start_dt, end_dt))
+            .map_err(|e| e.to_string())?;
+        let quotes = response.quotes().map_err(|e| e.to_string())?;
+
+        Ok(quotes
+            .into_iter()
+            .map(|q| Ohlcv {
+                _ts: None,
+                date: chrono::DateTime::from_timestamp(q.timestamp as i64, 0)
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+                open: q.open,
+                high: q.high,
+                low: q.low,
+                close: q.close,
+                volume: q.volume as f64,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,91 @@
+//! IPO lockup-expiration data and the window test the short thesis is predicated on:
+//! only take the short in the 1-3 calendar days before a name's lockup expires.
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IpoInfo {
+    pub symbol: String,
+    pub lockup_expiration_date: NaiveDate,
+}
+
+/// Source of IPO lockup-expiration dates, abstracting over where the feed comes from.
+pub trait LockupScreener {
+    fn load(&self) -> Result<Vec<IpoInfo>, String>;
+}
+
+/// Loads `symbol,lockup_expiration_date` rows from a local CSV feed.
+pub struct CsvLockupFeed {
+    path: String,
+}
+
+impl CsvLockupFeed {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl LockupScreener for CsvLockupFeed {
+    fn load(&self) -> Result<Vec<IpoInfo>, String> {
+        let mut rdr = csv::Reader::from_path(&self.path).map_err(|e| e.to_string())?;
+        let mut rows = Vec::new();
+        for result in rdr.deserialize() {
+            let rec: IpoInfo = result.map_err(|e| e.to_string())?;
+            rows.push(rec);
+        }
+        Ok(rows)
+    }
+}
+
+/// Loads a JSON array of `{ "symbol": ..., "lockup_expiration_date": "YYYY-MM-DD" }`.
+pub struct JsonLockupFeed {
+    path: String,
+}
+
+impl JsonLockupFeed {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl LockupScreener for JsonLockupFeed {
+    fn load(&self) -> Result<Vec<IpoInfo>, String> {
+        let data = std::fs::read_to_string(&self.path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&data).map_err(|e| e.to_string())
+    }
+}
+
+/// True when `symbol` has a lockup expiring 1-3 days after `today` — the signature
+/// window the strategy's short is supposed to fire in.
+pub fn within_lockup_window(lockups: &[IpoInfo], symbol: &str, today: NaiveDate) -> bool {
+    lockups.iter().any(|ipo| {
+        ipo.symbol == symbol && (1..=3).contains(&(ipo.lockup_expiration_date - today).num_days())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ipo(symbol: &str, expires_in_days: i64, today: NaiveDate) -> IpoInfo {
+        IpoInfo { symbol: symbol.to_string(), lockup_expiration_date: today + chrono::Duration::days(expires_in_days) }
+    }
+
+    #[test]
+    fn window_is_1_to_3_days_before_expiration() {
+        let today = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+        assert!(!within_lockup_window(&[ipo("ABC", 0, today)], "ABC", today), "gap of 0 (expires today) is outside the window");
+        assert!(within_lockup_window(&[ipo("ABC", 1, today)], "ABC", today), "gap of 1 is the near edge of the window");
+        assert!(within_lockup_window(&[ipo("ABC", 3, today)], "ABC", today), "gap of 3 is the far edge of the window");
+        assert!(!within_lockup_window(&[ipo("ABC", 4, today)], "ABC", today), "gap of 4 is outside the window");
+        assert!(!within_lockup_window(&[ipo("ABC", -1, today)], "ABC", today), "a lockup that already expired is outside the window");
+    }
+
+    #[test]
+    fn symbol_must_match() {
+        let today = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        assert!(!within_lockup_window(&[ipo("ABC", 2, today)], "XYZ", today));
+    }
+}
@@ -1,23 +1,31 @@
+mod data_source;
+mod lockup;
+mod portfolio;
+mod pricing;
+mod streaming;
+
 use chrono::NaiveDate;
+use data_source::{CsvSource, DataSource, YahooSource};
+use lockup::LockupScreener;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, Clone)]
-struct Ohlcv {
+pub(crate) struct Ohlcv {
     #[serde(with = "chrono::naive::serde::ts_seconds_option", default)]
     #[serde(skip_deserializing)]
-    _ts: Option<NaiveDate>,
+    pub(crate) _ts: Option<NaiveDate>,
     #[serde(rename = "date")]
-    date: String,
+    pub(crate) date: String,
     #[serde(rename = "open")]
-    open: f64,
+    pub(crate) open: f64,
     #[serde(rename = "high")]
-    high: f64,
+    pub(crate) high: f64,
     #[serde(rename = "low")]
-    low: f64,
+    pub(crate) low: f64,
     #[serde(rename = "close")]
-    close: f64,
+    pub(crate) close: f64,
     #[serde(rename = "volume")]
-    volume: f64,
+    pub(crate) volume: f64,
 }
 
 fn read_csv(path: &str) -> csv::Result<Vec<Ohlcv>> {
@@ -56,6 +64,47 @@ fn compute_rsi(closes: &[f64], period: usize) -> Vec<Option<f64>> {
     rsis
 }
 
+// Single-bar step of `reverse_rsi`: projects the next close for one bar.
+// Solves the RSI formula for the close that would drive RSI to `target_rsi` given
+// Wilder averages `auc`/`adc` (over `period` bars) ending at the bar priced `close`.
+// Shared by the batch `reverse_rsi` below and `streaming::ReverseRsiState` so both
+// project the same trigger price off the same averages.
+pub(crate) fn reverse_rsi_target_price(close: f64, auc: f64, adc: f64, period: usize, target_rsi: f64) -> f64 {
+    let x = (period as f64 - 1.0) * (adc * target_rsi / (100.0 - target_rsi) - auc);
+    if x >= 0.0 { close + x } else { close + x * (100.0 - target_rsi) / target_rsi }
+}
+
+// Mirrors the Wilder smoothing in `compute_rsi` (EMA with effective period 2*period-1)
+// but solves the RSI formula for the price instead of the other way around.
+fn reverse_rsi(closes: &[f64], target_rsi: f64, period: usize) -> Vec<Option<f64>> {
+    let mut projected: Vec<Option<f64>> = vec![None; closes.len()];
+    if closes.len() < period + 1 || period == 0 || target_rsi <= 0.0 || target_rsi >= 100.0 {
+        return projected;
+    }
+
+    let mut gains = 0.0;
+    let mut losses = 0.0;
+    for i in 1..=period {
+        let change = closes[i] - closes[i - 1];
+        if change >= 0.0 { gains += change; } else { losses -= change; }
+    }
+    let mut auc = gains / period as f64;
+    let mut adc = losses / period as f64;
+
+    projected[period] = Some(reverse_rsi_target_price(closes[period], auc, adc, period, target_rsi));
+
+    for i in (period + 1)..closes.len() {
+        let change = closes[i] - closes[i - 1];
+        let gain = change.max(0.0);
+        let loss = (-change).max(0.0);
+        auc = (auc * (period as f64 - 1.0) + gain) / period as f64;
+        adc = (adc * (period as f64 - 1.0) + loss) / period as f64;
+        projected[i] = Some(reverse_rsi_target_price(closes[i], auc, adc, period, target_rsi));
+    }
+
+    projected
+}
+
 fn volume_relative_high(volumes: &[f64], window: usize) -> Vec<bool> {
     if volumes.is_empty() { return vec![]; }
     let mut res = vec![false; volumes.len()];
@@ -70,24 +119,57 @@ fn volume_relative_high(volumes: &[f64], window: usize) -> Vec<bool> {
     res
 }
 
+// Rolling volume-weighted average price over the trailing `window` bars, using the
+// typical price (high + low + close) / 3 as the per-bar price weighted by volume.
+fn compute_vwap(ohlcv: &[Ohlcv], window: usize) -> Vec<Option<f64>> {
+    if ohlcv.is_empty() || window == 0 { return vec![None; ohlcv.len()]; }
+    let mut vwap = vec![None; ohlcv.len()];
+    for i in 0..ohlcv.len() {
+        if i + 1 < window { continue; }
+        let start = i + 1 - window;
+        let mut pv = 0.0;
+        let mut vol = 0.0;
+        for bar in &ohlcv[start..=i] {
+            let typical = (bar.high + bar.low + bar.close) / 3.0;
+            pv += typical * bar.volume;
+            vol += bar.volume;
+        }
+        vwap[i] = if vol > 0.0 { Some(pv / vol) } else { None };
+    }
+    vwap
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PositionSide { Short, Flat }
+
+// A long option standing in for the equity short, priced off BSM instead of the
+// underlying so PnL reflects a defined-risk expression of the same thesis.
 #[derive(Debug, Clone)]
-struct IpoInfo {
-    symbol: String,
-    lockup_expiration_date: NaiveDate,
+struct OptionLeg {
+    kind: pricing::OptionKind,
+    strike: f64,
+    entry_premium: f64,
+    expiry_years: f64,
+    implied_vol: f64,
 }
 
-fn ipo_lockup_screener_stub(today: NaiveDate) -> Vec<IpoInfo> {
-    // Placeholder: In real usage, fetch IPO and lockup data from API.
-    // Here we just return an empty list or a hardcoded example for demo.
-    let example = IpoInfo {
-        symbol: "DEMO".to_string(),
-        lockup_expiration_date: today, // treat as expiring today
-    };
-    vec![example]
+#[derive(Debug, Clone, Copy)]
+struct OptionStrategyConfig {
+    expiry_days: i64,
+    risk_free_rate: f64,
+    vol_window: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum PositionSide { Short, Flat }
+// Parses a `%Y-%m-%d` bar date, falling back to the Unix epoch for malformed rows
+// rather than failing the whole backtest over one bad date field.
+fn parse_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap_or_else(|_| NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+}
+
+fn mark_option_value(leg: &OptionLeg, cfg: &OptionStrategyConfig, closes: &[f64], entry_index: usize, at_index: usize) -> f64 {
+    let vol = pricing::realized_volatility(closes, at_index, cfg.vol_window).unwrap_or(leg.implied_vol);
+    pricing::mark_option_value(leg.kind, closes[at_index], leg.strike, leg.expiry_years, entry_index, at_index, vol, cfg.risk_free_rate)
+}
 
 #[derive(Debug, Clone)]
 struct Trade {
@@ -96,6 +178,15 @@ struct Trade {
     quantity: f64,
     entry_index: usize,
     exit_index: Option<usize>,
+    entry_date: NaiveDate,
+    // Calendar date the trade closed, so a multi-symbol portfolio replay (see
+    // `portfolio::evaluate`) can merge trades from different symbols in real
+    // chronological order instead of by bar index, which only orders within a symbol.
+    exit_date: Option<NaiveDate>,
+    option: Option<OptionLeg>,
+    // PnL this trade realized per unit of `quantity` (always 1.0 here), so the
+    // portfolio layer can rescale it once it picks a real position size.
+    pnl_per_unit: f64,
 }
 
 #[derive(Debug, Default)]
@@ -106,44 +197,80 @@ struct BacktestResult {
     losses: usize,
 }
 
-fn run_strategy(ohlcv: &[Ohlcv], symbol: &str) -> BacktestResult {
+fn run_strategy(ohlcv: &[Ohlcv], symbol: &str, options: Option<OptionStrategyConfig>, lockups: &[lockup::IpoInfo]) -> BacktestResult {
     if ohlcv.is_empty() { return BacktestResult::default(); }
     let closes: Vec<f64> = ohlcv.iter().map(|r| r.close).collect();
     let volumes: Vec<f64> = ohlcv.iter().map(|r| r.volume).collect();
     let rsi = compute_rsi(&closes, 14);
+    // `reverse_rsi(closes, 65.0/55.0, 14)[i]` is the close that, as of the *previous*
+    // bar's averages, would drive RSI to that level on bar i+1 — so indexing it at
+    // `i - 1` gives a trigger price known before bar i opens, letting entries/exits
+    // fire against that bar's high/low instead of waiting a full bar for its close.
+    let entry_trigger = reverse_rsi(&closes, 65.0, 14);
+    let exit_trigger = reverse_rsi(&closes, 55.0, 14);
     let vol_high = volume_relative_high(&volumes, 20);
+    // Rolling 20-bar VWAP. `vol_high` already gates entries on the same 20-bar
+    // warm-up (`volume_relative_high`'s `window`), so `vwap[i]` is never read as
+    // `None` while an entry could actually fire.
+    let vwap = compute_vwap(ohlcv, 20);
 
     let mut result = BacktestResult::default();
     let mut current: Option<Trade> = None;
 
     for i in 0..ohlcv.len() {
-        let date = &ohlcv[i].date;
-        let today = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap_or_else(|_| NaiveDate::from_ymd_opt(1970,1,1).unwrap());
-        let ipos = ipo_lockup_screener_stub(today);
-        let within_lockup_window = ipos.iter().any(|_ipo| {
-            // In a real screener compare symbol matches and days between today and lockup date in 1..=3
-            true
-        });
-
-        let rsi_ok = rsi[i].map(|v| v > 65.0).unwrap_or(false);
+        let today = parse_date(&ohlcv[i].date);
+        let within_lockup_window = lockup::within_lockup_window(lockups, symbol, today);
+
+        let rsi_ok = rsi[i].map(|v| v > 65.0).unwrap_or(false)
+            || (i > 0 && entry_trigger[i - 1].map(|trigger| ohlcv[i].high >= trigger).unwrap_or(false));
         let vol_ok = vol_high[i];
+        let vwap_overextended = vwap[i].map(|v| closes[i] > v).unwrap_or(false);
 
-        // Entry condition: RSI > 65, volume at relative high, IPO lockup 1-3 days (stubbed)
-        if current.is_none() && rsi_ok && vol_ok && within_lockup_window {
-            current = Some(Trade { entry_price: closes[i], exit_price: None, quantity: 1.0, entry_index: i, exit_index: None });
+        // Entry condition: RSI > 65, volume at relative high, price overextended above
+        // VWAP, in the 1-3 day window before this symbol's IPO lockup expires
+        if current.is_none() && rsi_ok && vol_ok && vwap_overextended && within_lockup_window {
+            let option = options.map(|cfg| {
+                let strike = closes[i];
+                let expiry_years = cfg.expiry_days as f64 / 365.0;
+                let implied_vol = pricing::realized_volatility(&closes, i, cfg.vol_window).unwrap_or(0.20);
+                let entry_premium = pricing::price(pricing::OptionKind::Put, closes[i], strike, expiry_years, cfg.risk_free_rate, implied_vol);
+                let put_greeks = pricing::greeks(pricing::OptionKind::Put, closes[i], strike, expiry_years, cfg.risk_free_rate, implied_vol);
+                // Put-call parity sanity check on entry: delta of the call we didn't buy
+                // should be this put's delta plus one.
+                let call_delta = pricing::greeks(pricing::OptionKind::Call, closes[i], strike, expiry_years, cfg.risk_free_rate, implied_vol).delta;
+                debug_assert!((call_delta - (put_greeks.delta + 1.0)).abs() < 1e-6);
+                println!(
+                    "{}: entered put strike={:.2} premium={:.2} delta={:.3} gamma={:.4} vega={:.3} theta={:.3} rho={:.3}",
+                    symbol, strike, entry_premium, put_greeks.delta, put_greeks.gamma, put_greeks.vega, put_greeks.theta, put_greeks.rho
+                );
+                OptionLeg { kind: pricing::OptionKind::Put, strike, entry_premium, expiry_years, implied_vol }
+            });
+            current = Some(Trade { entry_price: closes[i], exit_price: None, quantity: 1.0, entry_index: i, exit_index: None, entry_date: today, exit_date: None, option, pnl_per_unit: 0.0 });
         }
 
-        // Exit condition: RSI crosses back below 55 or simple take-profit/stop-loss
+        // Exit condition: RSI crosses back below 55, price reverts through VWAP, or
+        // simple take-profit/stop-loss
         if let Some(tr) = &mut current {
             let rsi_val = rsi[i];
             let take_profit = tr.entry_price * 0.97; // 3% move in favor for short
             let stop_loss = tr.entry_price * 1.03;    // 3% adverse move
             let price = closes[i];
-            let exit_signal = rsi_val.map(|v| v < 55.0).unwrap_or(false) || price <= take_profit || price >= stop_loss;
+            let vwap_reversion = vwap[i].map(|v| price <= v).unwrap_or(false);
+            let rsi_exit = rsi_val.map(|v| v < 55.0).unwrap_or(false)
+                || (i > 0 && exit_trigger[i - 1].map(|trigger| ohlcv[i].low <= trigger).unwrap_or(false));
+            let exit_signal = rsi_exit || vwap_reversion || price <= take_profit || price >= stop_loss;
             if exit_signal {
                 tr.exit_price = Some(price);
                 tr.exit_index = Some(i);
-                let pnl = (tr.entry_price - price) * tr.quantity; // short PnL
+                tr.exit_date = Some(today);
+                let pnl = match (&tr.option, options) {
+                    (Some(leg), Some(cfg)) => {
+                        let value = mark_option_value(leg, &cfg, &closes, tr.entry_index, i);
+                        (value - leg.entry_premium) * tr.quantity // long put: profit as value rises
+                    }
+                    _ => (tr.entry_price - price) * tr.quantity, // short PnL
+                };
+                tr.pnl_per_unit = pnl;
                 result.total_pnl += pnl;
                 if pnl >= 0.0 { result.wins += 1; } else { result.losses += 1; }
                 result.trades.push(tr.clone());
@@ -154,10 +281,19 @@ fn run_strategy(ohlcv: &[Ohlcv], symbol: &str) -> BacktestResult {
 
     // If position left open, close at last price
     if let Some(mut tr) = current {
+        let last_index = ohlcv.len() - 1;
         let last_price = *closes.last().unwrap();
         tr.exit_price = Some(last_price);
-        tr.exit_index = Some(ohlcv.len() - 1);
-        let pnl = (tr.entry_price - last_price) * tr.quantity;
+        tr.exit_index = Some(last_index);
+        tr.exit_date = Some(parse_date(&ohlcv[last_index].date));
+        let pnl = match (&tr.option, options) {
+            (Some(leg), Some(cfg)) => {
+                let value = mark_option_value(leg, &cfg, &closes, tr.entry_index, last_index);
+                (value - leg.entry_premium) * tr.quantity
+            }
+            _ => (tr.entry_price - last_price) * tr.quantity,
+        };
+        tr.pnl_per_unit = pnl;
         result.total_pnl += pnl;
         if pnl >= 0.0 { result.wins += 1; } else { result.losses += 1; }
         result.trades.push(tr);
@@ -167,19 +303,140 @@ fn run_strategy(ohlcv: &[Ohlcv], symbol: &str) -> BacktestResult {
     result
 }
 
+// Default option-overlay terms used when `--options` is passed: ~30-day puts priced
+// off a 20-bar realized-vol estimate at a 4% risk-free rate.
+fn default_option_config() -> OptionStrategyConfig {
+    OptionStrategyConfig { expiry_days: 30, risk_free_rate: 0.04, vol_window: 20 }
+}
+
+// Loads IPO lockup-expiration data for `within_lockup_window`, preferring the CSV
+// feed exported alongside `data/sample.csv` and falling back to a JSON feed (e.g.
+// pulled from a vendor API) if the CSV isn't present.
+fn load_lockups() -> Vec<lockup::IpoInfo> {
+    match lockup::CsvLockupFeed::new("data/lockups.csv").load() {
+        Ok(rows) => rows,
+        Err(csv_err) => match lockup::JsonLockupFeed::new("data/lockups.json").load() {
+            Ok(rows) => rows,
+            Err(json_err) => {
+                println!("Failed to load IPO lockup feed: {} (csv), {} (json)", csv_err, json_err);
+                Vec::new()
+            }
+        },
+    }
+}
+
 fn main() {
-    // Example usage: load CSV with columns: date,open,high,low,close,volume
-    let path = "data/sample.csv";
-    match read_csv(path) {
-        Ok(rows) => {
-            if rows.is_empty() {
-                println!("No data found in {}", path);
-                return;
+    // Universe of symbols to pull and backtest in one run. Pass `--live <SYM...>` to
+    // pull those symbols from Yahoo Finance instead of `data/sample.csv`, or
+    // `--stream <path>` to run the bounded-memory engine over a large local CSV. Add
+    // `--options` (combinable with either) to trade long puts off the BSM pricer
+    // instead of an equity short.
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let use_options = args.iter().any(|a| a == "--options");
+    args.retain(|a| a != "--options");
+    let options = use_options.then(default_option_config);
+
+    let lockups = load_lockups();
+    let portfolio_cfg = portfolio::PortfolioConfig { starting_equity: 100_000.0, risk_per_trade: 0.01 };
+
+    if args.first().map(String::as_str) == Some("--stream") {
+        let path = args.get(1).map(String::as_str).unwrap_or("data/sample.csv");
+        match streaming::run_strategy_streaming(path, "DEMO", options, &lockups) {
+            Ok(result) => {
+                let report = portfolio::evaluate(&[("DEMO".to_string(), result)], portfolio_cfg);
+                print_portfolio_report(&report);
             }
-            let _ = run_strategy(&rows, "DEMO");
+            Err(e) => println!("Failed to stream {}: {}", path, e),
         }
-        Err(e) => {
-            println!("Failed to read {}: {}", path, e);
+        return;
+    }
+
+    let (source, symbols): (Box<dyn DataSource>, Vec<String>) = if args.first().map(String::as_str) == Some("--live") {
+        (Box::new(YahooSource::new()), args[1..].to_vec())
+    } else {
+        (Box::new(CsvSource::new("data/sample.csv")), vec!["DEMO".to_string()])
+    };
+
+    let end = chrono::Utc::now().date_naive();
+    let start = end - chrono::Duration::days(365);
+
+    let mut results: Vec<(String, BacktestResult)> = Vec::new();
+    for symbol in &symbols {
+        match source.fetch(symbol, start, end) {
+            Ok(rows) => {
+                if rows.is_empty() {
+                    println!("No data found for {}", symbol);
+                    continue;
+                }
+                let result = run_strategy(&rows, symbol, options, &lockups);
+                results.push((symbol.clone(), result));
+            }
+            Err(e) => {
+                println!("Failed to fetch data for {}: {}", symbol, e);
+            }
         }
     }
+
+    let report = portfolio::evaluate(&results, portfolio_cfg);
+    print_portfolio_report(&report);
+}
+
+fn print_portfolio_report(report: &portfolio::PortfolioReport) {
+    println!(
+        "portfolio: ending_equity={:.2}, max_drawdown={:.2}%, sharpe={:.2}, profit_factor={:.2}, avg_win={:.2}, avg_loss={:.2}",
+        report.ending_equity,
+        report.max_drawdown * 100.0,
+        report.sharpe_ratio,
+        report.profit_factor,
+        report.avg_win,
+        report.avg_loss,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(close: f64) -> Ohlcv {
+        Ohlcv { _ts: None, date: "2024-01-01".to_string(), open: close, high: close, low: close, close, volume: 1_000.0 }
+    }
+
+    #[test]
+    fn reverse_rsi_projection_feeds_compute_rsi_to_target() {
+        let closes: Vec<f64> = vec![
+            44.0, 44.25, 44.5, 43.75, 44.65, 45.1, 45.0, 45.5, 46.0, 45.75, 46.4, 46.9, 47.2, 46.8, 46.5,
+        ];
+        let period = 14;
+        let projected = reverse_rsi(&closes, 65.0, period);
+        let trigger = projected[period].expect("projection available once the EMA is seeded");
+
+        let mut with_trigger = closes.clone();
+        with_trigger.push(trigger);
+        let rsi = compute_rsi(&with_trigger, period);
+        assert!((rsi[period + 1].unwrap() - 65.0).abs() < 1e-6, "projected close should drive RSI to the target");
+    }
+
+    #[test]
+    fn reverse_rsi_guards_degenerate_targets_and_short_history() {
+        let closes = vec![1.0, 2.0, 3.0];
+        assert_eq!(reverse_rsi(&closes, 65.0, 14), vec![None; closes.len()], "too few bars to seed the EMA");
+        assert_eq!(reverse_rsi(&closes, 0.0, 1), vec![None; closes.len()], "target_rsi == 0 must not divide by zero");
+        assert_eq!(reverse_rsi(&closes, 100.0, 1), vec![None; closes.len()], "target_rsi == 100 must not divide by zero");
+    }
+
+    #[test]
+    fn compute_vwap_matches_hand_computed_typical_price_average() {
+        let ohlcv = vec![bar(10.0), bar(20.0), bar(30.0)];
+        let vwap = compute_vwap(&ohlcv, 2);
+        assert_eq!(vwap[0], None, "window hasn't filled yet");
+        assert!((vwap[1].unwrap() - 15.0).abs() < 1e-9);
+        assert!((vwap[2].unwrap() - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compute_vwap_empty_or_zero_window_returns_all_none() {
+        assert_eq!(compute_vwap(&[], 5), Vec::<Option<f64>>::new());
+        assert_eq!(compute_vwap(&[bar(10.0)], 0), vec![None]);
+    }
+
 }
@@ -0,0 +1,195 @@
+//! Portfolio-level replay of one or more per-symbol `BacktestResult`s: sizes each
+//! trade from account equity and risk rather than the fixed `quantity = 1.0` each
+//! `BacktestResult` was produced with, and reports metrics comparable across symbols.
+
+use crate::{BacktestResult, Trade};
+
+// Matches the fixed 3% stop distance `run_strategy` uses for its take-profit/stop-loss.
+const STOP_DISTANCE_PCT: f64 = 0.03;
+
+/// Account-level settings driving position sizing in [`evaluate`].
+#[derive(Debug, Clone, Copy)]
+pub struct PortfolioConfig {
+    pub starting_equity: f64,
+    /// Fraction of current equity risked per trade, e.g. `0.01` for 1%.
+    pub risk_per_trade: f64,
+}
+
+/// Equity curve and risk metrics from a portfolio-level replay.
+#[derive(Debug, Clone, Default)]
+pub struct PortfolioReport {
+    pub equity_curve: Vec<f64>,
+    pub ending_equity: f64,
+    pub max_drawdown: f64,
+    pub sharpe_ratio: f64,
+    pub profit_factor: f64,
+    pub avg_win: f64,
+    pub avg_loss: f64,
+}
+
+/// Replays every trade across `results` against a single running equity balance,
+/// sizing each one with fixed-fractional risk off its entry price's 3% stop distance,
+/// or off the entry premium for an option leg (`trade.pnl_per_unit` is rescaled from the 1-unit quantity it was recorded with).
+pub fn evaluate(results: &[(String, BacktestResult)], cfg: PortfolioConfig) -> PortfolioReport {
+    let mut equity = cfg.starting_equity;
+    let mut equity_curve = vec![equity];
+    let mut trade_returns = Vec::new();
+    let mut wins = Vec::new();
+    let mut losses = Vec::new();
+
+    // Each symbol's own trades are already chronological, but concatenating symbols
+    // in array order would interleave their trades out of real time. Merge by the
+    // date each trade actually closed so overlapping symbols replay in the order
+    // their PnL would actually have landed on the account.
+    let mut trades: Vec<&Trade> = results.iter().flat_map(|(_symbol, result)| result.trades.iter()).collect();
+    trades.sort_by_key(|trade| trade.exit_date);
+
+    for trade in trades {
+        // An option leg's defined risk is the premium paid, not the 3% equity
+        // stop distance `run_strategy` uses for its underlying short — sizing
+        // a long put off `entry_price * 3%` would size it as if losses could
+        // exceed the premium, which they can't.
+        let stop_distance = match &trade.option {
+            Some(leg) => leg.entry_premium,
+            None => trade.entry_price * STOP_DISTANCE_PCT,
+        };
+        if stop_distance <= 0.0 || equity <= 0.0 {
+            continue;
+        }
+        let risk_dollars = equity * cfg.risk_per_trade;
+        let quantity = risk_dollars / stop_distance;
+        let pnl = trade.pnl_per_unit * quantity;
+
+        trade_returns.push(pnl / equity);
+        if pnl >= 0.0 { wins.push(pnl); } else { losses.push(pnl); }
+
+        equity += pnl;
+        equity_curve.push(equity);
+    }
+
+    let gross_win: f64 = wins.iter().sum();
+    let gross_loss: f64 = losses.iter().sum::<f64>().abs();
+
+    PortfolioReport {
+        max_drawdown: max_drawdown(&equity_curve),
+        sharpe_ratio: sharpe_ratio(&trade_returns),
+        profit_factor: if gross_loss > 0.0 { gross_win / gross_loss } else { f64::INFINITY },
+        avg_win: if wins.is_empty() { 0.0 } else { gross_win / wins.len() as f64 },
+        avg_loss: if losses.is_empty() { 0.0 } else { losses.iter().sum::<f64>() / losses.len() as f64 },
+        ending_equity: equity,
+        equity_curve,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::OptionKind;
+    use crate::OptionLeg;
+    use chrono::NaiveDate;
+
+    fn trade(entry_price: f64, pnl_per_unit: f64, exit_date: &str) -> Trade {
+        Trade {
+            entry_price,
+            exit_price: None,
+            quantity: 1.0,
+            entry_index: 0,
+            exit_index: Some(1),
+            entry_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            exit_date: Some(NaiveDate::parse_from_str(exit_date, "%Y-%m-%d").unwrap()),
+            option: None,
+            pnl_per_unit,
+        }
+    }
+
+    fn option_trade(entry_premium: f64, pnl_per_unit: f64, exit_date: &str) -> Trade {
+        let mut tr = trade(100.0, pnl_per_unit, exit_date);
+        tr.option = Some(OptionLeg { kind: OptionKind::Put, strike: 100.0, entry_premium, expiry_years: 0.25, implied_vol: 0.2 });
+        tr
+    }
+
+    fn result(trades: Vec<Trade>) -> BacktestResult {
+        BacktestResult { trades, total_pnl: 0.0, wins: 0, losses: 0 }
+    }
+
+    #[test]
+    fn sizes_an_equity_trade_off_the_3pct_stop_distance() {
+        let cfg = PortfolioConfig { starting_equity: 10_000.0, risk_per_trade: 0.01 };
+        // stop distance = 100 * 3% = 3.0, risk dollars = 10_000 * 1% = 100, so
+        // quantity = 100 / 3.0 and pnl = pnl_per_unit * quantity.
+        let report = evaluate(&[("A".to_string(), result(vec![trade(100.0, 3.0, "2024-01-02")]))], cfg);
+        let expected_quantity = (cfg.starting_equity * cfg.risk_per_trade) / (100.0 * STOP_DISTANCE_PCT);
+        let expected_pnl = 3.0 * expected_quantity;
+        assert!((report.ending_equity - (cfg.starting_equity + expected_pnl)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sizes_an_option_trade_off_its_premium_not_the_3pct_stop() {
+        let cfg = PortfolioConfig { starting_equity: 10_000.0, risk_per_trade: 0.01 };
+        // An option leg's risk is its premium, not entry_price * 3% - here the premium
+        // (5.0) is well above 100.0 * 3% (3.0), so the two sizings diverge clearly.
+        let report = evaluate(&[("A".to_string(), result(vec![option_trade(5.0, 2.0, "2024-01-02")]))], cfg);
+        let expected_quantity = (cfg.starting_equity * cfg.risk_per_trade) / 5.0;
+        let expected_pnl = 2.0 * expected_quantity;
+        assert!((report.ending_equity - (cfg.starting_equity + expected_pnl)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn evaluate_merges_trades_across_symbols_by_real_exit_date() {
+        let cfg = PortfolioConfig { starting_equity: 10_000.0, risk_per_trade: 0.01 };
+        // Symbol B's only trade closes before symbol A's, even though A is listed
+        // first - the equity curve should reflect B's pnl before A's.
+        let results = vec![
+            ("A".to_string(), result(vec![trade(100.0, 10.0, "2024-02-01")])),
+            ("B".to_string(), result(vec![trade(100.0, -10.0, "2024-01-01")])),
+        ];
+        let report = evaluate(&results, cfg);
+        assert!(report.equity_curve[1] < cfg.starting_equity, "B's losing trade (earlier exit) replays first");
+        assert!(report.ending_equity > report.equity_curve[1], "A's winning trade (later exit) replays second");
+    }
+
+    #[test]
+    fn max_drawdown_is_the_worst_peak_to_trough_decline() {
+        let curve = vec![100.0, 120.0, 90.0, 110.0];
+        assert!((max_drawdown(&curve) - (120.0 - 90.0) / 120.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sharpe_ratio_matches_hand_computed_mean_over_stddev() {
+        let returns = vec![0.01, 0.02, -0.01, 0.03];
+        let mean: f64 = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance: f64 = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+        let expected = mean / variance.sqrt();
+        assert!((sharpe_ratio(&returns) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sharpe_ratio_is_zero_for_fewer_than_two_returns() {
+        assert_eq!(sharpe_ratio(&[]), 0.0);
+        assert_eq!(sharpe_ratio(&[0.01]), 0.0);
+    }
+}
+
+fn max_drawdown(equity_curve: &[f64]) -> f64 {
+    let mut peak = f64::MIN;
+    let mut max_dd = 0.0_f64;
+    for &equity in equity_curve {
+        peak = peak.max(equity);
+        if peak > 0.0 {
+            max_dd = max_dd.max((peak - equity) / peak);
+        }
+    }
+    max_dd
+}
+
+// Per-trade Sharpe ratio (mean / stddev of per-trade returns, unannualized since
+// trades aren't spaced on a fixed calendar cadence).
+fn sharpe_ratio(returns: &[f64]) -> f64 {
+    if returns.len() < 2 {
+        return 0.0;
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    let std_dev = variance.sqrt();
+    if std_dev == 0.0 { 0.0 } else { mean / std_dev }
+}
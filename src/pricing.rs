@@ -0,0 +1,179 @@
+//! Black-Scholes-Merton pricing and Greeks for European options, used to mark
+//! option-based expressions of the core RSI/volume short thesis to market bar-to-bar.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Greeks {
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+}
+
+// Abramowitz & Stegun 7.1.26 approximation of the error function (max error ~1.5e-7).
+fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let a1 = 0.254829592;
+    let a2 = -0.284496736;
+    let a3 = 1.421413741;
+    let a4 = -1.453152027;
+    let a5 = 1.061405429;
+    let p = 0.3275911;
+    let t = 1.0 / (1.0 + p * x);
+    let y = 1.0 - ((((a5 * t + a4) * t + a3) * t + a2) * t + a1) * t * (-x * x).exp();
+    sign * y
+}
+
+fn norm_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / std::f64::consts::SQRT_2))
+}
+
+fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+fn d1_d2(spot: f64, strike: f64, rate: f64, vol: f64, t: f64) -> (f64, f64) {
+    let d1 = ((spot / strike).ln() + (rate + 0.5 * vol * vol) * t) / (vol * t.sqrt());
+    let d2 = d1 - vol * t.sqrt();
+    (d1, d2)
+}
+
+/// BSM fair value of a European option. Falls back to intrinsic value once there is
+/// no time or volatility left to price optionality.
+pub fn price(kind: OptionKind, spot: f64, strike: f64, t: f64, rate: f64, vol: f64) -> f64 {
+    if t <= 0.0 || vol <= 0.0 {
+        return match kind {
+            OptionKind::Call => (spot - strike).max(0.0),
+            OptionKind::Put => (strike - spot).max(0.0),
+        };
+    }
+    let (d1, d2) = d1_d2(spot, strike, rate, vol, t);
+    let disc = (-rate * t).exp();
+    match kind {
+        OptionKind::Call => spot * norm_cdf(d1) - strike * disc * norm_cdf(d2),
+        OptionKind::Put => strike * disc * norm_cdf(-d2) - spot * norm_cdf(-d1),
+    }
+}
+
+/// Delta, gamma, vega, theta (per day) and rho (per 1% rate move) for a European option.
+pub fn greeks(kind: OptionKind, spot: f64, strike: f64, t: f64, rate: f64, vol: f64) -> Greeks {
+    if t <= 0.0 || vol <= 0.0 {
+        return Greeks::default();
+    }
+    let (d1, d2) = d1_d2(spot, strike, rate, vol, t);
+    let disc = (-rate * t).exp();
+    let pdf_d1 = norm_pdf(d1);
+
+    let (delta, rho) = match kind {
+        OptionKind::Call => (norm_cdf(d1), strike * t * disc * norm_cdf(d2) / 100.0),
+        OptionKind::Put => (norm_cdf(d1) - 1.0, -strike * t * disc * norm_cdf(-d2) / 100.0),
+    };
+    let gamma = pdf_d1 / (spot * vol * t.sqrt());
+    let vega = spot * pdf_d1 * t.sqrt() / 100.0;
+    let theta = match kind {
+        OptionKind::Call => {
+            (-spot * pdf_d1 * vol / (2.0 * t.sqrt()) - rate * strike * disc * norm_cdf(d2)) / 365.0
+        }
+        OptionKind::Put => {
+            (-spot * pdf_d1 * vol / (2.0 * t.sqrt()) + rate * strike * disc * norm_cdf(-d2)) / 365.0
+        }
+    };
+
+    Greeks { delta, gamma, vega, theta, rho }
+}
+
+/// Marks a previously-entered option to market at `at_index`: bars elapsed since
+/// `entry_index` decay `expiry_years` toward zero, and `current_vol` (the caller's
+/// realized-vol estimate as of `at_index`) replaces the vol it was priced with at
+/// entry. Shared by the batch and streaming engines so both mark the same leg
+/// identically. `at_index - entry_index` counts trading days (bars), not calendar
+/// days, so it's annualized against the ~252 trading-day convention to match —
+/// dividing by 365 here would understate elapsed time and mark too much time value.
+pub fn mark_option_value(
+    kind: OptionKind,
+    spot: f64,
+    strike: f64,
+    expiry_years: f64,
+    entry_index: usize,
+    at_index: usize,
+    current_vol: f64,
+    rate: f64,
+) -> f64 {
+    let elapsed_years = (at_index - entry_index) as f64 / 252.0;
+    let remaining = (expiry_years - elapsed_years).max(0.0);
+    price(kind, spot, strike, remaining, rate, current_vol)
+}
+
+/// Annualized realized volatility from the log returns between the trailing `window`
+/// closes ending at `end_index` (inclusive), i.e. `window - 1` returns, used as the implied-vol input when no options chain is
+/// available. Returns `None` until enough closes exist.
+pub fn realized_volatility(closes: &[f64], end_index: usize, window: usize) -> Option<f64> {
+    if window < 2 || end_index + 1 < window {
+        return None;
+    }
+    let start = end_index + 1 - window;
+    let log_returns: Vec<f64> = (start + 1..=end_index)
+        .map(|i| (closes[i] / closes[i - 1]).ln())
+        .collect();
+    if log_returns.len() < 2 {
+        return None;
+    }
+    let mean = log_returns.iter().sum::<f64>() / log_returns.len() as f64;
+    let variance = log_returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (log_returns.len() - 1) as f64;
+    Some(variance.sqrt() * (252.0_f64).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_and_put_respect_put_call_parity() {
+        let (spot, strike, t, rate, vol) = (100.0, 95.0, 0.5, 0.04, 0.25);
+        let call = price(OptionKind::Call, spot, strike, t, rate, vol);
+        let put = price(OptionKind::Put, spot, strike, t, rate, vol);
+        // C - P = S - K*e^(-rt)
+        let lhs = call - put;
+        let rhs = spot - strike * (-rate * t).exp();
+        assert!((lhs - rhs).abs() < 1e-8);
+    }
+
+    #[test]
+    fn price_falls_back_to_intrinsic_value_at_expiry_or_zero_vol() {
+        assert_eq!(price(OptionKind::Call, 110.0, 100.0, 0.0, 0.04, 0.25), 10.0);
+        assert_eq!(price(OptionKind::Put, 90.0, 100.0, 0.0, 0.04, 0.25), 10.0);
+        assert_eq!(price(OptionKind::Call, 110.0, 100.0, 0.5, 0.04, 0.0), 10.0);
+    }
+
+    #[test]
+    fn greeks_deltas_satisfy_parity_and_zero_out_past_expiry() {
+        let (spot, strike, t, rate, vol) = (100.0, 95.0, 0.5, 0.04, 0.25);
+        let call = greeks(OptionKind::Call, spot, strike, t, rate, vol);
+        let put = greeks(OptionKind::Put, spot, strike, t, rate, vol);
+        assert!((call.delta - (put.delta + 1.0)).abs() < 1e-9, "call delta - put delta == 1");
+        assert!(call.gamma > 0.0 && put.gamma > 0.0, "gamma is positive for both legs of the same strike");
+
+        let expired = greeks(OptionKind::Call, spot, strike, 0.0, rate, vol);
+        assert_eq!(expired.delta, 0.0);
+        assert_eq!(expired.gamma, 0.0);
+    }
+
+    #[test]
+    fn realized_volatility_needs_window_closes_and_scales_by_sqrt_252() {
+        assert_eq!(realized_volatility(&[1.0, 2.0, 3.0], 2, 5), None, "too few closes to fill the window");
+
+        let closes = vec![100.0, 101.0, 99.0, 102.0, 98.0];
+        let vol = realized_volatility(&closes, closes.len() - 1, closes.len()).unwrap();
+        let log_returns: Vec<f64> = closes.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let mean = log_returns.iter().sum::<f64>() / log_returns.len() as f64;
+        let sample_var = log_returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (log_returns.len() - 1) as f64;
+        assert!((vol - sample_var.sqrt() * 252.0_f64.sqrt()).abs() < 1e-12);
+    }
+}
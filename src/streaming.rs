@@ -0,0 +1,445 @@
+//! Streaming alternative to the batch `run_strategy` path. Rows are consumed one at a
+//! time straight from the `csv::Reader` and every indicator keeps O(window) state
+//! instead of re-scanning slices of a fully materialized `Vec<Ohlcv>`, so memory stays
+//! bounded and huge multi-year/multi-symbol files run in a single pass.
+
+use crate::lockup::{self, IpoInfo};
+use crate::{pricing, BacktestResult, NaiveDate, Ohlcv, OptionLeg, OptionStrategyConfig, Trade};
+use std::collections::VecDeque;
+
+const RSI_PERIOD: usize = 14;
+const VOL_WINDOW: usize = 20;
+const VWAP_WINDOW: usize = 20;
+
+// Incremental Wilder RSI: same recurrence as `compute_rsi`, one bar at a time.
+struct RsiState {
+    period: usize,
+    prev_close: Option<f64>,
+    seed_gains: f64,
+    seed_losses: f64,
+    seed_count: usize,
+    avg_gain: f64,
+    avg_loss: f64,
+    seeded: bool,
+}
+
+impl RsiState {
+    fn new(period: usize) -> Self {
+        Self { period, prev_close: None, seed_gains: 0.0, seed_losses: 0.0, seed_count: 0, avg_gain: 0.0, avg_loss: 0.0, seeded: false }
+    }
+
+    fn push(&mut self, close: f64) -> Option<f64> {
+        let prev = self.prev_close.replace(close)?;
+        let change = close - prev;
+        if !self.seeded {
+            if change >= 0.0 { self.seed_gains += change; } else { self.seed_losses -= change; }
+            self.seed_count += 1;
+            if self.seed_count < self.period {
+                return None;
+            }
+            self.avg_gain = self.seed_gains / self.period as f64;
+            self.avg_loss = self.seed_losses / self.period as f64;
+            self.seeded = true;
+        } else {
+            let gain = change.max(0.0);
+            let loss = (-change).max(0.0);
+            self.avg_gain = (self.avg_gain * (self.period as f64 - 1.0) + gain) / self.period as f64;
+            self.avg_loss = (self.avg_loss * (self.period as f64 - 1.0) + loss) / self.period as f64;
+        }
+        let rs = if self.avg_loss == 0.0 { f64::INFINITY } else { self.avg_gain / self.avg_loss };
+        Some(100.0 - 100.0 / (1.0 + rs))
+    }
+}
+
+// Incremental analogue of `reverse_rsi`: the exact same Wilder seeding/update as
+// `RsiState`, but projects the close that would drive RSI to `target_rsi` on the next
+// bar instead of computing RSI itself, via the same `reverse_rsi_target_price` the
+// batch engine uses, so both fire on the same intrabar trigger crossings.
+struct ReverseRsiState {
+    period: usize,
+    target_rsi: f64,
+    prev_close: Option<f64>,
+    seed_gains: f64,
+    seed_losses: f64,
+    seed_count: usize,
+    auc: f64,
+    adc: f64,
+    seeded: bool,
+}
+
+impl ReverseRsiState {
+    fn new(period: usize, target_rsi: f64) -> Self {
+        Self { period, target_rsi, prev_close: None, seed_gains: 0.0, seed_losses: 0.0, seed_count: 0, auc: 0.0, adc: 0.0, seeded: false }
+    }
+
+    fn push(&mut self, close: f64) -> Option<f64> {
+        let prev = self.prev_close.replace(close)?;
+        let change = close - prev;
+        if !self.seeded {
+            if change >= 0.0 { self.seed_gains += change; } else { self.seed_losses -= change; }
+            self.seed_count += 1;
+            if self.seed_count < self.period {
+                return None;
+            }
+            self.auc = self.seed_gains / self.period as f64;
+            self.adc = self.seed_losses / self.period as f64;
+            self.seeded = true;
+        } else {
+            let gain = change.max(0.0);
+            let loss = (-change).max(0.0);
+            self.auc = (self.auc * (self.period as f64 - 1.0) + gain) / self.period as f64;
+            self.adc = (self.adc * (self.period as f64 - 1.0) + loss) / self.period as f64;
+        }
+        Some(crate::reverse_rsi_target_price(close, self.auc, self.adc, self.period, self.target_rsi))
+    }
+}
+
+// Monotonic decreasing deque of (index, volume) giving the max of the trailing
+// `window` bars (excluding the current one) in O(1) amortized per push.
+struct RollingMax {
+    window: usize,
+    deque: VecDeque<(usize, f64)>,
+}
+
+impl RollingMax {
+    fn new(window: usize) -> Self {
+        Self { window, deque: VecDeque::new() }
+    }
+
+    // Returns the max of the previous `window` bars before observing `value` at `index`.
+    fn push(&mut self, index: usize, value: f64) -> Option<f64> {
+        while matches!(self.deque.front(), Some((i, _)) if *i + self.window < index) {
+            self.deque.pop_front();
+        }
+        let max_prev = if index >= self.window { self.deque.front().map(|(_, v)| *v) } else { None };
+        while matches!(self.deque.back(), Some((_, v)) if *v <= value) {
+            self.deque.pop_back();
+        }
+        self.deque.push_back((index, value));
+        max_prev
+    }
+}
+
+// Ring buffer over (typical_price * volume, volume) pairs for a rolling VWAP.
+struct RollingVwap {
+    window: usize,
+    buf: VecDeque<(f64, f64)>,
+    sum_pv: f64,
+    sum_vol: f64,
+}
+
+impl RollingVwap {
+    fn new(window: usize) -> Self {
+        Self { window, buf: VecDeque::new(), sum_pv: 0.0, sum_vol: 0.0 }
+    }
+
+    fn push(&mut self, bar: &Ohlcv) -> Option<f64> {
+        let typical = (bar.high + bar.low + bar.close) / 3.0;
+        let pv = typical * bar.volume;
+        self.buf.push_back((pv, bar.volume));
+        self.sum_pv += pv;
+        self.sum_vol += bar.volume;
+        if self.buf.len() > self.window {
+            if let Some((old_pv, old_vol)) = self.buf.pop_front() {
+                self.sum_pv -= old_pv;
+                self.sum_vol -= old_vol;
+            }
+        }
+        if self.buf.len() < self.window || self.sum_vol <= 0.0 { None } else { Some(self.sum_pv / self.sum_vol) }
+    }
+}
+
+// Bounded ring buffer of the trailing `window` closes, used to estimate realized vol
+// for option marks without retaining the full close history. Delegates to
+// `pricing::realized_volatility` on that window so the streaming engine uses exactly
+// the same sample convention as the batch path instead of its own approximation.
+struct CloseRing {
+    window: usize,
+    buf: VecDeque<f64>,
+}
+
+impl CloseRing {
+    fn new(window: usize) -> Self {
+        Self { window, buf: VecDeque::with_capacity(window) }
+    }
+
+    fn push(&mut self, close: f64) {
+        self.buf.push_back(close);
+        if self.buf.len() > self.window {
+            self.buf.pop_front();
+        }
+    }
+
+    fn realized_volatility(&self) -> Option<f64> {
+        if self.buf.len() < self.window {
+            return None;
+        }
+        let closes: Vec<f64> = self.buf.iter().copied().collect();
+        pricing::realized_volatility(&closes, closes.len() - 1, self.window)
+    }
+}
+
+/// Streaming equivalent of `run_strategy`: same entry/exit rules, but driven by a
+/// `csv::Reader` instead of a pre-loaded `Vec<Ohlcv>`, with O(window) memory.
+pub fn run_strategy_streaming(path: &str, symbol: &str, options: Option<OptionStrategyConfig>, lockups: &[IpoInfo]) -> csv::Result<BacktestResult> {
+    let mut rdr = csv::Reader::from_path(path)?;
+
+    let mut rsi_state = RsiState::new(RSI_PERIOD);
+    // `entry_trigger`/`exit_trigger` in the batch engine: projects, as of the
+    // *previous* bar's Wilder averages, the close that would drive RSI to 65/55 on
+    // this bar, so a trigger crossing on this bar's high/low can fire a bar earlier
+    // than waiting for the plain RSI threshold on its close.
+    let mut entry_trigger_state = ReverseRsiState::new(RSI_PERIOD, 65.0);
+    let mut exit_trigger_state = ReverseRsiState::new(RSI_PERIOD, 55.0);
+    let mut prev_entry_trigger: Option<f64> = None;
+    let mut prev_exit_trigger: Option<f64> = None;
+    let mut vol_max = RollingMax::new(VOL_WINDOW);
+    let mut vwap = RollingVwap::new(VWAP_WINDOW);
+    // Mirror the option config's own realized-vol window (not the unrelated volume
+    // lookback above) so streaming marks agree with the batch engine's.
+    let mut close_ring = CloseRing::new(options.map(|cfg| cfg.vol_window).unwrap_or(VOL_WINDOW));
+
+    let mut result = BacktestResult::default();
+    let mut current: Option<Trade> = None;
+    let mut last_close = None;
+    let mut last_index = 0usize;
+    let mut last_date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+    for (i, record) in rdr.deserialize::<Ohlcv>().enumerate() {
+        let mut bar = record?;
+        bar.date = bar.date.trim().to_string();
+
+        let entry_trigger_hit = prev_entry_trigger.map(|trigger| bar.high >= trigger).unwrap_or(false);
+        let exit_trigger_hit = prev_exit_trigger.map(|trigger| bar.low <= trigger).unwrap_or(false);
+
+        let rsi_val = rsi_state.push(bar.close);
+        let vol_max_prev = vol_max.push(i, bar.volume);
+        let vol_ok = vol_max_prev.map(|max_prev| bar.volume > max_prev).unwrap_or(false);
+        let vwap_val = vwap.push(&bar);
+        close_ring.push(bar.close);
+        prev_entry_trigger = entry_trigger_state.push(bar.close);
+        prev_exit_trigger = exit_trigger_state.push(bar.close);
+
+        let today = crate::parse_date(&bar.date);
+        last_close = Some(bar.close);
+        last_index = i;
+        last_date = today;
+        let within_lockup_window = lockup::within_lockup_window(lockups, symbol, today);
+
+        let rsi_ok = rsi_val.map(|v| v > 65.0).unwrap_or(false) || entry_trigger_hit;
+        let vwap_overextended = vwap_val.map(|v| bar.close > v).unwrap_or(false);
+
+        if current.is_none() && rsi_ok && vol_ok && vwap_overextended && within_lockup_window {
+            let option = options.map(|cfg| {
+                let strike = bar.close;
+                let expiry_years = cfg.expiry_days as f64 / 365.0;
+                let implied_vol = close_ring.realized_volatility().unwrap_or(0.20);
+                let entry_premium = pricing::price(pricing::OptionKind::Put, bar.close, strike, expiry_years, cfg.risk_free_rate, implied_vol);
+                OptionLeg { kind: pricing::OptionKind::Put, strike, entry_premium, expiry_years, implied_vol }
+            });
+            current = Some(Trade { entry_price: bar.close, exit_price: None, quantity: 1.0, entry_index: i, exit_index: None, entry_date: today, exit_date: None, option, pnl_per_unit: 0.0 });
+        }
+
+        if let Some(tr) = &mut current {
+            let take_profit = tr.entry_price * 0.97;
+            let stop_loss = tr.entry_price * 1.03;
+            let price = bar.close;
+            let vwap_reversion = vwap_val.map(|v| price <= v).unwrap_or(false);
+            let exit_signal = rsi_val.map(|v| v < 55.0).unwrap_or(false) || exit_trigger_hit || vwap_reversion || price <= take_profit || price >= stop_loss;
+            if exit_signal {
+                tr.exit_price = Some(price);
+                tr.exit_index = Some(i);
+                tr.exit_date = Some(today);
+                let pnl = match (&tr.option, options) {
+                    (Some(leg), Some(cfg)) => {
+                        let vol = close_ring.realized_volatility().unwrap_or(leg.implied_vol);
+                        let value = pricing::mark_option_value(leg.kind, price, leg.strike, leg.expiry_years, tr.entry_index, i, vol, cfg.risk_free_rate);
+                        (value - leg.entry_premium) * tr.quantity
+                    }
+                    _ => (tr.entry_price - price) * tr.quantity,
+                };
+                tr.pnl_per_unit = pnl;
+                result.total_pnl += pnl;
+                if pnl >= 0.0 { result.wins += 1; } else { result.losses += 1; }
+                result.trades.push(tr.clone());
+                current = None;
+            }
+        }
+    }
+
+    if let Some(mut tr) = current {
+        if let Some(last_price) = last_close {
+            tr.exit_price = Some(last_price);
+            tr.exit_index = Some(last_index);
+            tr.exit_date = Some(last_date);
+            let pnl = match (&tr.option, options) {
+                (Some(leg), Some(cfg)) => {
+                    let vol = close_ring.realized_volatility().unwrap_or(leg.implied_vol);
+                    let value = pricing::mark_option_value(leg.kind, last_price, leg.strike, leg.expiry_years, tr.entry_index, last_index, vol, cfg.risk_free_rate);
+                    (value - leg.entry_premium) * tr.quantity
+                }
+                _ => (tr.entry_price - last_price) * tr.quantity,
+            };
+            tr.pnl_per_unit = pnl;
+            result.total_pnl += pnl;
+            if pnl >= 0.0 { result.wins += 1; } else { result.losses += 1; }
+            result.trades.push(tr);
+        }
+    }
+
+    println!("{}: trades={}, pnl={:.2}, wins={}, losses={} (streaming)", symbol, result.trades.len(), result.total_pnl, result.wins, result.losses);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{default_option_config, read_csv, run_strategy};
+    use std::io::Write;
+
+    #[test]
+    fn rsi_state_matches_compute_rsi() {
+        let closes = vec![44.0, 44.25, 44.5, 43.75, 44.65, 45.1, 45.0, 45.5, 46.0, 45.75, 46.4, 46.9, 47.2, 46.8, 46.5];
+        let expected = crate::compute_rsi(&closes, RSI_PERIOD);
+
+        let mut state = RsiState::new(RSI_PERIOD);
+        let streamed: Vec<Option<f64>> = closes.iter().map(|&c| state.push(c)).collect();
+
+        for (e, s) in expected.iter().zip(streamed.iter()) {
+            match (e, s) {
+                (Some(e), Some(s)) => assert!((e - s).abs() < 1e-9),
+                (None, None) => {}
+                _ => panic!("RsiState diverged from compute_rsi: {:?} vs {:?}", e, s),
+            }
+        }
+    }
+
+    #[test]
+    fn rolling_max_excludes_the_current_bar_and_respects_the_window() {
+        let mut roll = RollingMax::new(3);
+        assert_eq!(roll.push(0, 10.0), None, "no prior bars yet");
+        assert_eq!(roll.push(1, 5.0), None, "window not full until index >= window");
+        assert_eq!(roll.push(2, 8.0), None);
+        assert_eq!(roll.push(3, 1.0), Some(10.0), "max of bars 0..3");
+        assert_eq!(roll.push(4, 2.0), Some(8.0), "bar 0 has scrolled out of the window");
+    }
+
+    #[test]
+    fn rolling_vwap_matches_compute_vwap_over_the_same_window() {
+        let ohlcv = vec![bar(10.0), bar(20.0), bar(30.0), bar(40.0)];
+        let expected = crate::compute_vwap(&ohlcv, 2);
+
+        let mut roll = RollingVwap::new(2);
+        let streamed: Vec<Option<f64>> = ohlcv.iter().map(|bar| roll.push(bar)).collect();
+
+        for (e, s) in expected.iter().zip(streamed.iter()) {
+            match (e, s) {
+                (Some(e), Some(s)) => assert!((e - s).abs() < 1e-9),
+                (None, None) => {}
+                _ => panic!("RollingVwap diverged from compute_vwap: {:?} vs {:?}", e, s),
+            }
+        }
+    }
+
+    fn bar(close: f64) -> Ohlcv {
+        Ohlcv { _ts: None, date: "2024-01-01".to_string(), open: close, high: close, low: close, close, volume: 1_000.0 }
+    }
+
+    // A steady uptrend with strictly increasing volume: RSI pins near 100 past bar
+    // 14, every bar is a 20-bar volume high past bar 20, and price stays above the
+    // rolling VWAP throughout, so an entry fires once the lockup window opens and a
+    // stop-loss exit fires a few bars later — enough to exercise the option-marking
+    // path this module shares with `run_strategy`.
+    fn write_uptrend_csv() -> std::path::PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("trading_bot_streaming_equivalence_{}.csv", std::process::id()));
+        let mut f = std::fs::File::create(&path).unwrap();
+        writeln!(f, "date,open,high,low,close,volume").unwrap();
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        for i in 0..35i64 {
+            let close = 100.0 + i as f64;
+            let volume = 1_000.0 + 50.0 * i as f64;
+            let date = start + chrono::Duration::days(i);
+            writeln!(f, "{},{:.2},{:.2},{:.2},{:.2},{:.2}", date.format("%Y-%m-%d"), close, close, close, close, volume).unwrap();
+        }
+        path
+    }
+
+    // Oscillating closes (+0.4/-0.25) keep RSI moderate (about 61 to 65) through
+    // bar 20 — never crossing the plain 65 threshold — but bar 20's intrabar high
+    // jumps well past the entry trigger projected off bar 19's averages, so only the
+    // `entry_trigger` path (not `rsi_val > 65.0`) opens a trade. VWAP and the lockup
+    // window already qualify at bar 19, but the 20-bar volume high first qualifies at
+    // bar 20, so it's the earliest bar either engine could possibly enter on. Without the streaming engine also tracking the
+    // trigger, it would miss this entry entirely while the batch engine takes it.
+    fn write_trigger_fixture_csv() -> std::path::PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("trading_bot_streaming_trigger_fixture_{}.csv", std::process::id()));
+        let mut f = std::fs::File::create(&path).unwrap();
+        writeln!(f, "date,open,high,low,close,volume").unwrap();
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+        let mut close = 100.0;
+        for i in 0..30i64 {
+            if i > 0 {
+                close += if i % 2 == 1 { 0.4 } else { -0.25 };
+            }
+            let high = if i == 20 { close + 2.6 } else { close };
+            let volume = 1_000.0 + 50.0 * i as f64;
+            let date = start + chrono::Duration::days(i);
+            writeln!(f, "{},{:.2},{:.2},{:.2},{:.2},{:.2}", date.format("%Y-%m-%d"), close, high, close, close, volume).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn streaming_matches_batch_when_the_entry_trigger_fires_ahead_of_plain_rsi() {
+        let path = write_trigger_fixture_csv();
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let lockups = vec![lockup::IpoInfo {
+            symbol: "DEMO".to_string(),
+            lockup_expiration_date: start + chrono::Duration::days(22),
+        }];
+        let options = Some(default_option_config());
+
+        let rows = read_csv(path.to_str().unwrap()).unwrap();
+        let batch = run_strategy(&rows, "DEMO", options, &lockups);
+        let streamed = run_strategy_streaming(path.to_str().unwrap(), "DEMO", options, &lockups).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(!batch.trades.is_empty(), "fixture should enter on the trigger before plain RSI ever crosses 65");
+        assert_eq!(batch.trades[0].entry_index, 20, "the trigger should fire exactly at bar 20, not a plain RSI crossing later");
+        assert_eq!(batch.trades.len(), streamed.trades.len());
+        for (b, s) in batch.trades.iter().zip(streamed.trades.iter()) {
+            assert_eq!(b.entry_index, s.entry_index);
+            assert_eq!(b.exit_index, s.exit_index);
+            assert!((b.pnl_per_unit - s.pnl_per_unit).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn streaming_matches_batch_on_the_same_csv() {
+        let path = write_uptrend_csv();
+        let lockups = vec![lockup::IpoInfo {
+            symbol: "DEMO".to_string(),
+            lockup_expiration_date: NaiveDate::from_ymd_opt(2024, 1, 24).unwrap(),
+        }];
+        let options = Some(default_option_config());
+
+        let rows = read_csv(path.to_str().unwrap()).unwrap();
+        let batch = run_strategy(&rows, "DEMO", options, &lockups);
+        let streamed = run_strategy_streaming(path.to_str().unwrap(), "DEMO", options, &lockups).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(!batch.trades.is_empty(), "fixture should actually exercise a trade");
+        assert_eq!(batch.trades.len(), streamed.trades.len());
+        assert_eq!(batch.wins, streamed.wins);
+        assert_eq!(batch.losses, streamed.losses);
+        assert!((batch.total_pnl - streamed.total_pnl).abs() < 1e-9);
+        for (b, s) in batch.trades.iter().zip(streamed.trades.iter()) {
+            assert_eq!(b.entry_index, s.entry_index);
+            assert_eq!(b.exit_index, s.exit_index);
+            assert!((b.pnl_per_unit - s.pnl_per_unit).abs() < 1e-9);
+        }
+    }
+}